@@ -1,12 +1,41 @@
 use ::libc::c_void;
 use ::std::collections::HashMap;
 use ::std::ffi::CString;
+use ::std::sync::RwLock;
+
+use ::error::Error;
+use ::os::imp;
+use ::symbol::Symbol;
 
 /// A handle wrapper with a VTable of symbols found in the library as they're requested.
 pub struct Library {
     pub(crate) handle: *mut c_void,
     pub(crate) name:   String,
-    pub(crate) table:  HashMap<String, *mut c_void>,
+    pub(crate) table:  RwLock<HashMap<String, *mut c_void>>,
+}
+
+// SAFETY: `handle` is an opaque pointer owned solely by this `Library` and never mutated after
+// construction, so reading it from another thread is fine. `table` is an `RwLock`, so concurrent
+// `sym` calls are synchronized through it, and the one piece of truly global state they touch --
+// the `dlerror()` channel -- is itself serialized by a private mutex in `os::unix`'s `guarded`
+// helper.
+unsafe impl Send for Library {}
+unsafe impl Sync for Library {}
+
+/// The symbol and owning shared object found for an address by [`Library::address_info`], as
+/// reported by `dladdr`.
+pub struct SymbolInfo {
+    /// The name of the nearest symbol at or before the address, if the owning object exports one.
+    pub symbol_name: Option<String>,
+
+    /// The exact address of `symbol_name`, which may differ from the address that was looked up.
+    pub symbol_addr: *mut c_void,
+
+    /// The pathname of the shared object containing the address.
+    pub object_path: String,
+
+    /// The load base address of the shared object containing the address.
+    pub object_base: *mut c_void,
 }
 
 impl Library {
@@ -17,11 +46,15 @@ impl Library {
         &self.handle
     }
 
-    /// Returns an unsafe reference to the VTable of the library.
+    /// Returns a snapshot of the VTable of the library.
     /// Usually, the [`sym`] method is enough, though you're free to use it for what you may want
-    /// to use it for.
-    pub unsafe fn table(&self) -> &HashMap<String, *mut c_void> {
-        &self.table
+    /// to use it for. This is a clone of the cache taken under the read lock rather than a live
+    /// guard, so the lock is released before this returns: `std::sync::RwLock` isn't reentrant,
+    /// so holding a read guard across a [`sym`] call on the same thread would deadlock the moment
+    /// `sym` needed the write lock to cache a newly resolved symbol, and there would be no way to
+    /// enforce that at the call site.
+    pub unsafe fn table(&self) -> HashMap<String, *mut c_void> {
+        self.table.read().unwrap().clone()
     }
 
     /// Returns the path of the dynamic library as given to [`open`] or [`new`].
@@ -29,71 +62,75 @@ impl Library {
         self.name.clone()
     }
 
+    /// Looks up the symbol and owning shared object for an address via `dladdr`, which is useful
+    /// for building stack-trace symbolizers and plugin diagnostics on top of this crate. It pairs
+    /// naturally with [`sym`], since you can round-trip a resolved [`Symbol`](::symbol::Symbol)'s
+    /// address back to its declared name.
+    ///
+    /// Returns `None` if the address couldn't be resolved, or `dladdr` isn't available on this
+    /// platform.
+    ///
+    /// `addr` is dereferenced by the underlying `dladdr` call, so like [`handle`] and [`table`]
+    /// this is `unsafe`: the caller must ensure it's a valid address to look up.
+    pub unsafe fn address_info(&self, addr: *const c_void) -> Option<SymbolInfo> {
+        imp::dladdr(addr)
+    }
+
     /// Requests and finds a symbol in the library.
     /// So long [`RtldOr::Local`] wasn't specified, it will also look at other loaded symbols which
     /// are either in the program itself or were loaded with [`RtldOr::Global`].
-    pub fn sym<T>(&mut self, name: String) -> Option<Box<T>> {
-        if self.table.contains_key(&name) {
-            let gotten = self.table.get(&name);
-            unsafe {
-                let gotten = Box::from_raw(*gotten.unwrap() as *mut T);
-                return Some(gotten)
-            }
-        }
-        if self.handle.is_null() {
-            return None
-        }
-        let name_c = CString::new(name.as_str());
-        if name_c.is_err() {
-            return None
-        }
-        let name_c = name_c.unwrap();
-        let sym: Box<T>;
-        unsafe {
-            let sym_c: *mut ::libc::c_void;
-            if name.is_empty() {
-                let name_c: *const ::libc::c_char = ::std::ptr::null();
-                sym_c = ::libc::dlsym(self.handle, name_c);
-            } else {
-                sym_c = ::libc::dlsym(self.handle, name_c.as_ptr());
-            }
-            sym = Box::from_raw(sym_c as *mut T);
-            self.table.insert(name, sym_c);
+    ///
+    /// The returned [`Symbol`] borrows `self`, so it can never outlive the [`Library`] it was
+    /// resolved from.
+    pub fn sym<'lib, T>(&'lib self, name: String) -> Result<Symbol<'lib, T>, Error> {
+        if let Some(gotten) = self.table.read().unwrap().get(&name) {
+            return Ok(unsafe { Symbol::new(*gotten) });
         }
-        Some(sym)
+        let name_c = CString::new(name.as_str()).map_err(|_| Error::InvalidCString)?;
+        let sym_c = unsafe { imp::sym(self.handle, &name_c, name.is_empty())? };
+        self.table.write().unwrap().insert(name, sym_c);
+        Ok(unsafe { Symbol::new(sym_c) })
     }
 
     /// Makes a safe instance using [`new`] then applies the closure given to it.
-    /// It returns true if it was successful, or false if anything went wrong.
-    pub fn open(name: String, flags: ::RtldValue, closure: fn(lib: Library)) -> bool {
-        let cstr = CString::new(name.as_str());
-        if cstr.is_err() {
-            return false
-        }
-        let cstr = cstr.unwrap();
+    pub fn open(name: String, flags: ::RtldValue, closure: fn(lib: Library)) -> Result<(), Error> {
+        let cstr = CString::new(name.as_str()).map_err(|_| Error::InvalidCString)?;
         unsafe {
-            let lib = Self::new(cstr, flags);
-            if lib.is_none() {
-                return false
-            }
-            closure(lib.unwrap());
+            let lib = Self::new(cstr, flags)?;
+            closure(lib);
         }
-        true
+        Ok(())
     }
 
     /// Creates a new instance of the library wanted. There are no checks other than
     /// handle checks upon loading it. It is the user's responsibility to pass a valid CString
     /// instance and valid flags.
-    pub unsafe fn new(name_c: CString, flags: ::RtldValue) -> Option<Self> {
-        let handle = ::libc::dlopen(name_c.as_ptr(), flags.to_libc());
-        if handle.is_null() {
-            return None
-        }
+    pub unsafe fn new(name_c: CString, flags: ::RtldValue) -> Result<Self, Error> {
+        let handle = imp::open(&name_c, &flags)?;
 
-        Some(Self {
+        Ok(Self {
             handle,
             name:  name_c.into_string().unwrap(), // dlopen expects UTF-8, so this will never fail
-            table: HashMap::new(),
+            table: RwLock::new(HashMap::new()),
+        })
+    }
+
+    /// Opens a handle to the running program's own symbol table, rather than to a library on
+    /// disk. On unix this is `dlopen(NULL, ...)`, giving the global symbol scope: combined with a
+    /// [`RtldOr::Global`]-loaded library, [`sym`] can resolve symbols that were statically linked
+    /// into the main binary *or* exported by other already-loaded modules, without needing a path
+    /// to a library on disk.
+    ///
+    /// On Windows this is narrower: `GetModuleHandleExW` only hands back a handle to the main
+    /// executable's own module, so [`sym`] will only resolve symbols the EXE itself exports, not
+    /// ones loaded in-process via another DLL.
+    pub fn this(flags: ::RtldValue) -> Result<Self, Error> {
+        let handle = unsafe { imp::open_self(&flags)? };
+
+        Ok(Self {
+            handle,
+            name:  String::new(),
+            table: RwLock::new(HashMap::new()),
         })
     }
 }
@@ -101,10 +138,65 @@ impl Library {
 impl ::std::ops::Drop for Library {
     /// Closes the handle then drops the entire library.
     fn drop(&mut self) {
-        if !self.handle.is_null() {
-            unsafe {
-                ::libc::dlclose(self.handle);
-            }
+        unsafe {
+            imp::close(self.handle);
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use ::rtld::{RtldMain, RtldValue};
+    use ::std::os::raw::{c_char, c_int};
+
+    /// Resolving `strlen` from the process's own symbol table and calling it through the returned
+    /// [`Symbol`] must hand back a real function pointer into libc, not a dangling or
+    /// double-freed one -- this is the soundness property `Symbol<'lib, T>` exists to uphold.
+    #[test]
+    fn sym_resolves_and_calls_a_real_function() {
+        let lib = Library::this(RtldValue::new(RtldMain::Now)).expect("open self");
+        let strlen: Symbol<unsafe extern "C" fn(*const c_char) -> usize> =
+            lib.sym("strlen".to_owned()).expect("resolve strlen");
+        let text = b"hello\0";
+        assert_eq!(unsafe { strlen(text.as_ptr() as *const c_char) }, 5);
+    }
+
+    /// `dladdr` on the resolved `strlen` pointer should round-trip back to a library owning it,
+    /// exercising [`Library::address_info`] against a symbol obtained via [`Library::sym`].
+    #[test]
+    fn address_info_round_trips_a_resolved_symbol() {
+        let lib = Library::this(RtldValue::new(RtldMain::Now)).expect("open self");
+        let strlen: Symbol<unsafe extern "C" fn(*const c_char) -> c_int> =
+            lib.sym("strlen".to_owned()).expect("resolve strlen");
+        let info = unsafe { lib.address_info(*strlen as *const c_void) };
+        assert!(info.is_some());
+    }
+
+    /// Many threads resolving (and re-resolving) symbols through a single shared `Library`
+    /// concurrently must neither deadlock nor corrupt the cache nor race on the process-global
+    /// `dlerror()` channel -- the properties `Send`/`Sync` and the `dlerror` mutex are meant to
+    /// guarantee.
+    #[test]
+    fn sym_is_safe_to_call_from_many_threads_concurrently() {
+        use ::std::sync::Arc;
+        use ::std::thread;
+
+        let lib = Arc::new(Library::this(RtldValue::new(RtldMain::Now)).expect("open self"));
+        let handles: Vec<_> = (0..16)
+            .map(|i| {
+                let lib = Arc::clone(&lib);
+                thread::spawn(move || {
+                    let name = if i % 2 == 0 { "strlen" } else { "strcmp" };
+                    for _ in 0..100 {
+                        let _sym: Symbol<unsafe extern "C" fn()> =
+                            lib.sym(name.to_owned()).expect("resolve symbol");
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().expect("thread panicked");
         }
     }
 }
@@ -0,0 +1,49 @@
+use ::std::ffi::CString;
+use ::std::fmt;
+
+/// Errors that can occur while loading a dynamic library or resolving a symbol from it.
+#[derive(Debug)]
+pub enum Error {
+    /// `dlopen` failed; `desc` holds the description returned by `dlerror()` (on Windows, the
+    /// `FormatMessageW` text for `GetLastError()` after `LoadLibraryExW` failed).
+    DlOpen { desc: CString },
+
+    /// `dlsym` failed; `desc` holds the description returned by `dlerror()` (on Windows, the
+    /// `FormatMessageW` text for `GetLastError()` after `GetProcAddress` failed).
+    DlSym { desc: CString },
+
+    /// The given name could not be converted into a `CString` because it contained an interior
+    /// nul byte, or (on Windows) a library name was not valid UTF-8.
+    InvalidCString,
+
+    /// An empty name was passed to [`Library::sym`](::dynlib::Library::sym) to request the
+    /// handle's default symbol (the null-name `dlsym` convention), but the current platform has
+    /// no equivalent of that lookup. `GetProcAddress` on Windows, for example, has no null-name
+    /// convention and would just fail the lookup outright, so this is reported distinctly instead
+    /// of masquerading as a generic [`Error::DlSym`].
+    DefaultSymbolUnsupported,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::DlOpen { ref desc } => write!(f, "dlopen failed: {}", desc.to_string_lossy()),
+            Error::DlSym { ref desc } => write!(f, "dlsym failed: {}", desc.to_string_lossy()),
+            Error::InvalidCString => write!(f, "name contained an interior nul byte or was not valid UTF-8"),
+            Error::DefaultSymbolUnsupported => {
+                write!(f, "the current platform has no default-symbol lookup")
+            }
+        }
+    }
+}
+
+impl ::std::error::Error for Error {
+    fn description(&self) -> &str {
+        match *self {
+            Error::DlOpen { .. } => "dlopen failed",
+            Error::DlSym { .. } => "dlsym failed",
+            Error::InvalidCString => "name contained an interior nul byte or was not valid UTF-8",
+            Error::DefaultSymbolUnsupported => "the current platform has no default-symbol lookup",
+        }
+    }
+}
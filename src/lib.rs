@@ -1,8 +1,16 @@
 extern crate libc;
+#[cfg(windows)]
+extern crate winapi;
 
 pub use ::rtld::{RtldValue, RtldMain, RtldOr};
+#[cfg(unix)]
 pub use ::libc::{dlopen as unsafe_open, dlsym as unsafe_sym};
 pub use ::dynlib::Library;
+pub use ::error::Error;
+pub use ::symbol::Symbol;
 
 pub mod rtld;
 pub mod dynlib;
+pub mod error;
+pub mod symbol;
+mod os;
@@ -0,0 +1,14 @@
+//! Platform-specific dynamic loading primitives.
+//!
+//! [`Library`](::dynlib::Library) is entirely platform-agnostic; it only ever talks to whichever
+//! of these modules matches the target platform through the `imp` alias below.
+
+#[cfg(unix)]
+pub(crate) mod unix;
+#[cfg(windows)]
+pub(crate) mod windows;
+
+#[cfg(unix)]
+pub(crate) use self::unix as imp;
+#[cfg(windows)]
+pub(crate) use self::windows as imp;
@@ -0,0 +1,130 @@
+use ::libc::c_void;
+use ::std::ffi::{CStr, CString};
+use ::std::sync::{Mutex, Once};
+
+use ::dynlib::SymbolInfo;
+use ::error::Error;
+use ::rtld::RtldValue;
+
+/// The raw handle type returned by `dlopen`.
+pub(crate) type RawHandle = *mut c_void;
+
+static DLERROR_MUTEX_INIT: Once = Once::new();
+static mut DLERROR_MUTEX: *const Mutex<()> = ::std::ptr::null();
+
+/// On modern glibc/musl/BSD libc implementations, `dlerror()`'s error state is thread-local, so
+/// two threads calling `dl*` functions concurrently can't observe each other's error. POSIX
+/// doesn't actually guarantee that, though, so this mutex serializes the clear-call-read sequence
+/// below as a fallback for libc implementations that still share it process-wide; on the common,
+/// thread-local case it just adds an uncontended lock/unlock.
+fn dlerror_lock() -> &'static Mutex<()> {
+    unsafe {
+        DLERROR_MUTEX_INIT.call_once(|| {
+            DLERROR_MUTEX = Box::into_raw(Box::new(Mutex::new(()))) as *const Mutex<()>;
+        });
+        &*DLERROR_MUTEX
+    }
+}
+
+/// Clears any stale `dlerror()` state (reading it resets it), invokes `f`, then reads `dlerror()`
+/// again. A successful `dl*` call doesn't reset a pending error, so without the clearing read an
+/// old error could be mistaken for one raised by `f`. Only a null post-call
+/// `dlerror()` is treated as success: on some platforms `dlsym` returns a valid-looking pointer
+/// for a symbol whose real value is null, so the post-call check takes priority over whatever `f`
+/// returned.
+unsafe fn guarded<R, F>(f: F) -> Result<R, CString>
+    where F: FnOnce() -> R
+{
+    let _guard = dlerror_lock().lock().unwrap();
+    ::libc::dlerror();
+    let ret = f();
+    let err = ::libc::dlerror();
+    if err.is_null() {
+        Ok(ret)
+    } else {
+        Err(CStr::from_ptr(err).to_owned())
+    }
+}
+
+pub(crate) unsafe fn open(name: &CStr, flags: &RtldValue) -> Result<RawHandle, Error> {
+    let handle = guarded(|| ::libc::dlopen(name.as_ptr(), flags.to_libc()))
+        .map_err(|desc| Error::DlOpen { desc })?;
+    Ok(handle)
+}
+
+/// Opens the running program's own symbol scope via `dlopen(NULL, ...)`.
+pub(crate) unsafe fn open_self(flags: &RtldValue) -> Result<RawHandle, Error> {
+    let handle = guarded(|| ::libc::dlopen(::std::ptr::null(), flags.to_libc()))
+        .map_err(|desc| Error::DlOpen { desc })?;
+    Ok(handle)
+}
+
+pub(crate) unsafe fn sym(handle: RawHandle, name: &CStr, use_default: bool) -> Result<*mut c_void, Error> {
+    if use_default {
+        let null_name: *const ::libc::c_char = ::std::ptr::null();
+        guarded(|| ::libc::dlsym(handle, null_name))
+            .map_err(|desc| Error::DlSym { desc })
+    } else {
+        guarded(|| ::libc::dlsym(handle, name.as_ptr()))
+            .map_err(|desc| Error::DlSym { desc })
+    }
+}
+
+pub(crate) unsafe fn close(handle: RawHandle) {
+    if !handle.is_null() {
+        ::libc::dlclose(handle);
+    }
+}
+
+/// `dladdr` is a glibc/BSD extension, not part of POSIX, so it's only available on the unix
+/// targets `libc` exposes it for. Everywhere else this resolves to the "unavailable" stub below.
+#[cfg(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+    target_os = "dragonfly",
+    target_os = "solaris",
+))]
+pub(crate) unsafe fn dladdr(addr: *const c_void) -> Option<SymbolInfo> {
+    let mut info: ::libc::Dl_info = ::std::mem::zeroed();
+    if ::libc::dladdr(addr, &mut info) == 0 {
+        return None;
+    }
+
+    let symbol_name = if info.dli_sname.is_null() {
+        None
+    } else {
+        Some(CStr::from_ptr(info.dli_sname).to_string_lossy().into_owned())
+    };
+    let object_path = if info.dli_fname.is_null() {
+        String::new()
+    } else {
+        CStr::from_ptr(info.dli_fname).to_string_lossy().into_owned()
+    };
+
+    Some(SymbolInfo {
+        symbol_name,
+        symbol_addr: info.dli_saddr,
+        object_path,
+        object_base: info.dli_fbase,
+    })
+}
+
+#[cfg(not(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+    target_os = "dragonfly",
+    target_os = "solaris",
+)))]
+pub(crate) unsafe fn dladdr(_addr: *const c_void) -> Option<SymbolInfo> {
+    None
+}
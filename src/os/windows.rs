@@ -0,0 +1,111 @@
+use ::libc::c_void;
+use ::std::ffi::{CStr, CString, OsStr, OsString};
+use ::std::os::windows::ffi::{OsStrExt, OsStringExt};
+use ::std::ptr;
+
+use ::winapi::shared::minwindef::HMODULE;
+use ::winapi::um::errhandlingapi::GetLastError;
+use ::winapi::um::libloaderapi::{
+    FreeLibrary, GetModuleHandleExW, GetProcAddress, LoadLibraryExW, LOAD_WITH_ALTERED_SEARCH_PATH,
+};
+use ::winapi::um::winbase::{
+    FormatMessageW, FORMAT_MESSAGE_FROM_SYSTEM, FORMAT_MESSAGE_IGNORE_INSERTS,
+};
+
+use ::dynlib::SymbolInfo;
+use ::error::Error;
+use ::rtld::RtldValue;
+
+/// The raw handle type returned by `LoadLibraryExW`, stored as an opaque pointer so the rest of
+/// the crate doesn't need to know about `HMODULE`.
+pub(crate) type RawHandle = *mut c_void;
+
+fn widen(s: &str) -> Vec<u16> {
+    OsStr::new(s).encode_wide().chain(Some(0)).collect()
+}
+
+/// Maps the parts of [`RtldValue`] that have a Windows analogue onto `LOAD_LIBRARY_*` flag bits.
+/// Windows has no notion of RTLD's global/local symbol scope, so [`Global`](::rtld::RtldOr::Global),
+/// [`Local`](::rtld::RtldOr::Local), [`NoDelete`](::rtld::RtldOr::NoDelete) and
+/// [`DeepBind`](::rtld::RtldOr::DeepBind) have nothing to map to and are ignored.
+/// [`NoLoad`](::rtld::RtldOr::NoLoad) is also ignored rather than mapped to
+/// `LOAD_LIBRARY_AS_DATAFILE`: that flag loads the file anyway, just non-executably, which is the
+/// opposite of "don't load it, just hand me a handle if it's already loaded", and `GetProcAddress`
+/// against such a handle isn't supported. `LOAD_WITH_ALTERED_SEARCH_PATH` is always set because
+/// this crate is always given an explicit path, which is exactly the case that flag is for.
+fn to_windows_flags(_flags: &RtldValue) -> u32 {
+    LOAD_WITH_ALTERED_SEARCH_PATH
+}
+
+fn last_error_desc() -> CString {
+    unsafe {
+        let code = GetLastError();
+        let mut buf = [0u16; 512];
+        let len = FormatMessageW(
+            FORMAT_MESSAGE_FROM_SYSTEM | FORMAT_MESSAGE_IGNORE_INSERTS,
+            ptr::null(),
+            code,
+            0,
+            buf.as_mut_ptr(),
+            buf.len() as u32,
+            ptr::null_mut(),
+        );
+        let msg = if len == 0 {
+            format!("unknown error {}", code)
+        } else {
+            OsString::from_wide(&buf[..len as usize])
+                .to_string_lossy()
+                .trim_end()
+                .to_owned()
+        };
+        CString::new(msg).unwrap_or_else(|_| CString::new("???").unwrap())
+    }
+}
+
+pub(crate) unsafe fn open(name: &CStr, flags: &RtldValue) -> Result<RawHandle, Error> {
+    let name = name.to_str().map_err(|_| Error::InvalidCString)?;
+    let wide = widen(name);
+    let handle = LoadLibraryExW(wide.as_ptr(), ptr::null_mut(), to_windows_flags(flags));
+    if handle.is_null() {
+        return Err(Error::DlOpen { desc: last_error_desc() });
+    }
+    Ok(handle as RawHandle)
+}
+
+/// Opens a handle to the running executable's own module, the closest Windows analogue to
+/// `dlopen(NULL, ...)`. `GetModuleHandleExW` with no flags increments the module's reference
+/// count just like `LoadLibrary` does, so closing it via `FreeLibrary` later is correct.
+pub(crate) unsafe fn open_self(_flags: &RtldValue) -> Result<RawHandle, Error> {
+    let mut handle: HMODULE = ptr::null_mut();
+    if GetModuleHandleExW(0, ptr::null(), &mut handle) == 0 {
+        return Err(Error::DlOpen { desc: last_error_desc() });
+    }
+    Ok(handle as RawHandle)
+}
+
+pub(crate) unsafe fn sym(handle: RawHandle, name: &CStr, use_default: bool) -> Result<*mut c_void, Error> {
+    // Unlike `dlsym`, `GetProcAddress` has no null-name convention for "the handle's default
+    // symbol" -- passing it an empty string would just fail the lookup with a misleading
+    // `DlSym` error, so report the platform gap explicitly instead.
+    if use_default {
+        return Err(Error::DefaultSymbolUnsupported);
+    }
+    let addr = GetProcAddress(handle as HMODULE, name.as_ptr());
+    if addr.is_null() {
+        Err(Error::DlSym { desc: last_error_desc() })
+    } else {
+        Ok(addr as *mut c_void)
+    }
+}
+
+pub(crate) unsafe fn close(handle: RawHandle) {
+    if !handle.is_null() {
+        FreeLibrary(handle as HMODULE);
+    }
+}
+
+/// Windows has no `dladdr` equivalent wired up here (it would need `DbgHelp`'s `SymFromAddr`),
+/// so address lookups are always unresolved on this platform.
+pub(crate) unsafe fn dladdr(_addr: *const c_void) -> Option<SymbolInfo> {
+    None
+}
@@ -53,6 +53,7 @@ impl RtldMain {
 }
 
 /// The RTLD OR values to be used with the OR operator (|).
+#[derive(PartialEq)]
 pub enum RtldOr {
     /// The symbols defined in the library will be made available to all other subsequently loaded
     /// libraries. Be careful as this does not make them available to previously loaded ones.
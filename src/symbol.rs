@@ -0,0 +1,45 @@
+use ::libc::c_void;
+use ::std::marker::PhantomData;
+use ::std::mem::size_of;
+use ::std::ops::Deref;
+
+/// A symbol resolved from a [`Library`](::dynlib::Library), borrowed for as long as the library
+/// that produced it is alive.
+///
+/// `dlsym` hands back a pointer into the loaded library's own mapped image (its code or data
+/// segments), not an allocation `Symbol` owns. Because of that, `Symbol` never deallocates its
+/// pointer on drop, and the `'lib` lifetime ties every resolved symbol to the `&self` borrow of
+/// the [`Library`](::dynlib::Library) it came from, so the borrow checker rejects a `Symbol` that
+/// would outlive the library (whose `Drop` calls `dlclose`).
+pub struct Symbol<'lib, T: 'lib> {
+    pub(crate) ptr:     *mut c_void,
+    pub(crate) _marker: PhantomData<&'lib T>,
+}
+
+impl<'lib, T> Symbol<'lib, T> {
+    /// Compile-time assertion that `T` is exactly pointer-sized. This rejects fat pointers and
+    /// oversized types at the call site, since `dlsym` only ever hands back a single pointer-sized
+    /// value and nothing else could be reconstructed from it.
+    const ASSERT_POINTER_SIZED: usize = 0 - (size_of::<T>() != size_of::<*mut c_void>()) as usize;
+
+    /// Wraps a raw symbol pointer as resolved by `dlsym`. The caller is responsible for ensuring
+    /// `ptr` was actually resolved from the library that `'lib` borrows.
+    pub(crate) unsafe fn new(ptr: *mut c_void) -> Self {
+        let _ = Self::ASSERT_POINTER_SIZED;
+        Symbol {
+            ptr,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'lib, T> Deref for Symbol<'lib, T> {
+    type Target = T;
+
+    /// Reinterprets the raw symbol pointer as `T` without reading through it: for the common case
+    /// of `T` being an `unsafe extern fn(...)`, the bits `dlsym` returned *are* the function
+    /// pointer's value, not the address of a `T` to load from.
+    fn deref(&self) -> &T {
+        unsafe { &*(&self.ptr as *const *mut c_void as *const T) }
+    }
+}